@@ -0,0 +1,101 @@
+use encoding_rs::WINDOWS_1252;
+use unicode_normalization::UnicodeNormalization;
+
+/// Re-decode runs of text that look like mis-decoded Latin-1/Windows-1252
+/// byte sequences, then apply Unicode NFC normalization and strip leftover
+/// control characters.
+pub fn normalize_text(text: &str) -> String {
+  redecode_mojibake_runs(text)
+    .nfc()
+    .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
+    .collect()
+}
+
+/// Re-decode only the maximal runs of characters in the Latin-1 range
+/// (U+0080-U+00FF), the only runs where casting `char` back to `u8` is lossless.
+fn redecode_mojibake_runs(text: &str) -> String {
+  let mut output = String::with_capacity(text.len());
+  let mut run: Vec<char> = Vec::new();
+
+  for c in text.chars() {
+    if is_mojibake_candidate(c) {
+      run.push(c);
+    } else {
+      flush_run(&mut run, &mut output);
+      output.push(c);
+    }
+  }
+  flush_run(&mut run, &mut output);
+
+  output
+}
+
+fn flush_run(run: &mut Vec<char>, output: &mut String) {
+  if run.is_empty() {
+    return;
+  }
+  match redecode_run(run) {
+    Some(fixed) => output.push_str(&fixed),
+    None => output.extend(run.iter()),
+  }
+  run.clear();
+}
+
+fn is_mojibake_candidate(c: char) -> bool {
+  (0x80..=0xFF).contains(&(c as u32))
+}
+
+/// Recover a run's original text via a UTF-8 round-trip, falling back to
+/// Windows-1252; `None` if neither decodes cleanly shorter than the run.
+fn redecode_run(run: &[char]) -> Option<String> {
+  // A lone Latin-1 char is as likely to be legitimate as mangled.
+  if run.len() < 2 {
+    return None;
+  }
+
+  let bytes: Vec<u8> = run.iter().map(|c| *c as u8).collect();
+
+  if let Ok(utf8) = String::from_utf8(bytes.clone()) {
+    if utf8.chars().count() < run.len() {
+      return Some(utf8);
+    }
+  }
+
+  let (decoded, _, had_errors) = WINDOWS_1252.decode(&bytes);
+  if !had_errors && decoded.chars().count() < run.len() {
+    return Some(decoded.into_owned());
+  }
+
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn redecodes_utf8_mis_decoded_as_latin1() {
+    // "café" (UTF-8 bytes 63 61 66 C3 A9) with its last two bytes
+    // mis-decoded one byte at a time as Latin-1 reads as "cafÃ©".
+    assert_eq!(normalize_text("cafÃ©"), "café");
+  }
+
+  #[test]
+  fn leaves_legitimate_non_latin1_text_untouched() {
+    let text = "Résumé — 日本語 — café";
+    assert_eq!(normalize_text(text), text);
+  }
+
+  #[test]
+  fn leaves_a_lone_latin1_char_untouched() {
+    assert_eq!(redecode_run(&['é']), None);
+  }
+
+  #[test]
+  fn leaves_an_undecodable_run_untouched() {
+    // Two Latin-1 chars whose bytes aren't valid UTF-8, and whose
+    // Windows-1252 decoding is no shorter than the run, are left as-is
+    // rather than guessed at.
+    assert_eq!(redecode_run(&['\u{81}', '\u{8d}']), None);
+  }
+}
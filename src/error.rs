@@ -0,0 +1,95 @@
+/// Machine-readable classification for extraction failures, so JS callers
+/// can branch on `code` instead of pattern-matching an error string.
+#[napi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+  /// The file couldn't be read from disk (missing, permissions, etc.).
+  ReadError,
+  /// The PDF is encrypted and requires a password we don't have.
+  PasswordProtected,
+  /// The PDF's object structure couldn't be parsed. Also returned when a
+  /// later processing step fails: an invalid chunk configuration, a
+  /// tokenizer that can't be loaded, or a failed OCR pass.
+  ParseError,
+  /// Parsing succeeded but produced no usable text (e.g. an image-only PDF).
+  EmptyText,
+  /// The worker thread doing the extraction crashed (panic, OOM, etc.),
+  /// rather than the PDF itself being unreadable.
+  InternalError,
+}
+
+/// Attaches `ErrorCode` to the thrown JS error as `err.code`, via `napi::Error`'s
+/// generic `status` field, instead of leaving callers to string-parse `reason`.
+impl AsRef<str> for ErrorCode {
+  fn as_ref(&self) -> &str {
+    match self {
+      ErrorCode::ReadError => "ReadError",
+      ErrorCode::PasswordProtected => "PasswordProtected",
+      ErrorCode::ParseError => "ParseError",
+      ErrorCode::EmptyText => "EmptyText",
+      ErrorCode::InternalError => "InternalError",
+    }
+  }
+}
+
+/// An extraction failure carrying a machine-readable `code` alongside the
+/// human-readable `message`.
+#[derive(Debug)]
+pub struct ExtractError {
+  pub code: ErrorCode,
+  pub message: String,
+}
+
+impl ExtractError {
+  pub fn read(message: impl Into<String>) -> Self {
+    ExtractError {
+      code: ErrorCode::ReadError,
+      message: message.into(),
+    }
+  }
+
+  pub fn password_protected(message: impl Into<String>) -> Self {
+    ExtractError {
+      code: ErrorCode::PasswordProtected,
+      message: message.into(),
+    }
+  }
+
+  pub fn parse(message: impl Into<String>) -> Self {
+    ExtractError {
+      code: ErrorCode::ParseError,
+      message: message.into(),
+    }
+  }
+
+  pub fn empty_text(message: impl Into<String>) -> Self {
+    ExtractError {
+      code: ErrorCode::EmptyText,
+      message: message.into(),
+    }
+  }
+
+  pub fn internal(message: impl Into<String>) -> Self {
+    ExtractError {
+      code: ErrorCode::InternalError,
+      message: message.into(),
+    }
+  }
+
+  /// Classify a raw `pdf_extract`/`lopdf` error message by its text, since
+  /// those crates don't expose a typed error enum.
+  pub fn from_parse_failure(message: impl Into<String>) -> Self {
+    let message = message.into();
+    if message.to_lowercase().contains("password") || message.to_lowercase().contains("encrypt") {
+      ExtractError::password_protected(message)
+    } else {
+      ExtractError::parse(message)
+    }
+  }
+}
+
+impl From<ExtractError> for napi::Error<ErrorCode> {
+  fn from(e: ExtractError) -> Self {
+    napi::Error::new(e.code, e.message)
+  }
+}
@@ -1,11 +1,23 @@
 #![deny(clippy::all)]
 
+use napi::bindgen_prelude::Buffer;
 use tokio::{fs, task};
 use text_splitter::{ChunkConfig, TextSplitter};
 
 #[macro_use]
 extern crate napi_derive;
 
+mod encoding;
+mod error;
+mod ocr;
+mod page_extract;
+mod tokenizer_chunks;
+
+pub use error::ErrorCode;
+pub use ocr::OcrOptions;
+
+use error::ExtractError;
+
 #[napi(object)]
 pub struct Page {
   pub page: u32,
@@ -18,19 +30,82 @@ pub struct TextChunk {
   pub text: String,
 }
 
+#[napi(object)]
+pub struct PagesResult {
+  pub pages: Vec<Page>,
+  pub failed_pages: Vec<u32>,
+}
+
+#[napi(object)]
+pub struct ChunksResult {
+  pub chunks: Vec<TextChunk>,
+  pub failed_pages: Vec<u32>,
+}
+
 #[napi]
-pub async fn extract_text_from_pdf(path: String) -> napi::Result<String> {
+pub async fn extract_text_from_pdf(
+  path: String,
+  normalize: Option<bool>,
+) -> napi::Result<String, ErrorCode> {
   let bytes = read_file_async(&path).await?;
   let text = extract_text_from_bytes(&path, bytes).await?;
   let cleaned = clean_text(&text);
-  Ok(cleaned)
+
+  if cleaned.trim().is_empty() {
+    return Err(ExtractError::empty_text(format!(
+      "No extractable text in '{}' (likely an image-only or scanned PDF)",
+      path
+    ))
+    .into());
+  }
+
+  Ok(if normalize.unwrap_or(false) {
+    clean_text_normalized(&cleaned)
+  } else {
+    cleaned
+  })
 }
 
+/// Like `extract_text_from_pdf`, but when the text layer is too sparse
+/// (scanned papers, exported slides with no embedded text) falls back to
+/// rasterizing each page and recovering the text via OCR.
 #[napi]
-pub async fn extract_text_pages(path: String) -> napi::Result<Vec<Page>> {
+pub async fn extract_text_with_ocr(
+  path: String,
+  options: Option<OcrOptions>,
+) -> napi::Result<String, ErrorCode> {
   let bytes = read_file_async(&path).await?;
-  let text = extract_text_from_bytes(&path, bytes).await?;
-  Ok(split_text_into_pages(&text))
+  let text = extract_text_from_bytes(&path, bytes.clone()).await?;
+  let cleaned = clean_text(&text);
+  let options = options.unwrap_or_default();
+
+  task::spawn_blocking(move || ocr::recover_with_ocr(&bytes, &cleaned, &options))
+    .await
+    .map_err(|e| ExtractError::internal(format!("OCR thread panicked for '{}': {}", path, e)))?
+    .map_err(|e| ExtractError::from_parse_failure(format!("Failed to recover text via OCR for '{}': {}", path, e)))
+    .map_err(napi::Error::from)
+}
+
+#[napi]
+pub async fn extract_text_pages(path: String) -> napi::Result<Vec<Page>, ErrorCode> {
+  let bytes = read_file_async(&path).await?;
+  task::spawn_blocking(move || page_extract::extract_pages_from_bytes(&bytes))
+    .await
+    .map_err(|e| ExtractError::internal(format!("Page extraction thread panicked for '{}': {}", path, e)))?
+    .map_err(napi::Error::from)
+}
+
+/// Like `extract_text_pages`, but tolerates a malformed page, reporting its
+/// number in `failedPages` instead of failing the whole job.
+#[napi]
+pub async fn extract_text_pages_best_effort(path: String) -> napi::Result<PagesResult, ErrorCode> {
+  let bytes = read_file_async(&path).await?;
+  let (pages, failed_pages) = task::spawn_blocking(move || page_extract::extract_pages_best_effort(&bytes))
+    .await
+    .map_err(|e| ExtractError::internal(format!("Page extraction thread panicked for '{}': {}", path, e)))?
+    .map_err(napi::Error::from)?;
+
+  Ok(PagesResult { pages, failed_pages })
 }
 
 #[napi]
@@ -38,54 +113,152 @@ pub async fn extract_text_chunks(
   path: String,
   chunk_size: u32,
   chunk_overlap: u32,
-) -> napi::Result<Vec<TextChunk>> {
+) -> napi::Result<Vec<TextChunk>, ErrorCode> {
   let bytes = read_file_async(&path).await?;
   let text = extract_text_from_bytes(&path, bytes).await?;
   let cleaned = clean_text(&text);
+  build_chunks(&cleaned, chunk_size, chunk_overlap)
+}
 
-  let config = ChunkConfig::new(chunk_size as usize)
-    .with_overlap(chunk_overlap as usize)
-    .map_err(|e| napi::Error::from_reason(format!(
-      "Invalid chunk config (chunk_size={}, overlap={}): {}",
-      chunk_size, chunk_overlap, e
-    )))?;
+/// Like `extract_text_chunks`, but chunks whichever pages extracted cleanly,
+/// reporting the rest in `failedPages` instead of failing the whole job.
+#[napi]
+pub async fn extract_text_chunks_best_effort(
+  path: String,
+  chunk_size: u32,
+  chunk_overlap: u32,
+) -> napi::Result<ChunksResult, ErrorCode> {
+  let bytes = read_file_async(&path).await?;
+  let (pages, failed_pages) = task::spawn_blocking(move || page_extract::extract_pages_best_effort(&bytes))
+    .await
+    .map_err(|e| ExtractError::internal(format!("Page extraction thread panicked for '{}': {}", path, e)))?
+    .map_err(napi::Error::from)?;
 
-  let splitter = TextSplitter::new(config);
-  let result = splitter
-    .chunks(&cleaned)
-    .into_iter()
-    .enumerate()
-    .map(|(i, text)| TextChunk {
-      id: i as u32,
-      text: text.to_string(),
-    })
-    .collect();
+  let combined = pages.into_iter().map(|p| p.text).collect::<Vec<_>>().join("\n\n");
+  let cleaned = clean_text(&combined);
+  let chunks = build_chunks(&cleaned, chunk_size, chunk_overlap)?;
 
-  Ok(result)
+  Ok(ChunksResult { chunks, failed_pages })
+}
+
+/// Like `extract_text_chunks`, but bounds chunk size in tokens (measured by
+/// a named HuggingFace tokenizer) rather than raw characters, for feeding
+/// directly into an embedding/LLM pipeline's context window.
+#[napi]
+pub async fn extract_token_chunks(
+  path: String,
+  max_tokens: u32,
+  overlap: u32,
+  tokenizer_name: String,
+) -> napi::Result<Vec<TextChunk>, ErrorCode> {
+  let bytes = read_file_async(&path).await?;
+  let text = extract_text_from_bytes(&path, bytes).await?;
+  let cleaned = clean_text(&text);
+
+  task::spawn_blocking(move || {
+    tokenizer_chunks::chunk_by_tokens(&cleaned, max_tokens as usize, overlap as usize, &tokenizer_name)
+  })
+  .await
+  .map_err(|e| ExtractError::internal(format!("Tokenizer chunking thread panicked for '{}': {}", path, e)))?
+  .map_err(|e| ExtractError::parse(format!("Failed to chunk '{}' by tokens: {}", path, e)))
+  .map_err(napi::Error::from)
+}
+
+/// Label used in error messages for functions that operate on in-memory
+/// buffers rather than a filesystem path.
+const BUFFER_LABEL: &str = "<in-memory buffer>";
+
+/// Like `extract_text_from_pdf`, but takes PDF bytes directly instead of a
+/// filesystem path, for callers (HTTP uploads, S3 objects, decrypted blobs)
+/// that already have the bytes in memory.
+#[napi]
+pub async fn extract_text_from_buffer(
+  bytes: Buffer,
+  normalize: Option<bool>,
+) -> napi::Result<String, ErrorCode> {
+  let text = extract_text_from_bytes(BUFFER_LABEL, bytes.to_vec()).await?;
+  let cleaned = clean_text(&text);
+
+  if cleaned.trim().is_empty() {
+    return Err(ExtractError::empty_text(
+      "No extractable text in buffer (likely an image-only or scanned PDF)",
+    )
+    .into());
+  }
+
+  Ok(if normalize.unwrap_or(false) {
+    clean_text_normalized(&cleaned)
+  } else {
+    cleaned
+  })
+}
+
+/// Like `extract_text_pages`, but takes PDF bytes directly instead of a
+/// filesystem path.
+#[napi]
+pub async fn extract_text_pages_from_buffer(bytes: Buffer) -> napi::Result<Vec<Page>, ErrorCode> {
+  let bytes = bytes.to_vec();
+  task::spawn_blocking(move || page_extract::extract_pages_from_bytes(&bytes))
+    .await
+    .map_err(|e| ExtractError::internal(format!("Page extraction thread panicked for '{}': {}", BUFFER_LABEL, e)))?
+    .map_err(napi::Error::from)
+}
+
+/// Like `extract_text_chunks`, but takes PDF bytes directly instead of a
+/// filesystem path.
+#[napi]
+pub async fn extract_text_chunks_from_buffer(
+  bytes: Buffer,
+  chunk_size: u32,
+  chunk_overlap: u32,
+) -> napi::Result<Vec<TextChunk>, ErrorCode> {
+  let text = extract_text_from_bytes(BUFFER_LABEL, bytes.to_vec()).await?;
+  let cleaned = clean_text(&text);
+  build_chunks(&cleaned, chunk_size, chunk_overlap)
 }
 
 /// Async file read with descriptive error
-async fn read_file_async(path: &str) -> napi::Result<Vec<u8>> {
+async fn read_file_async(path: &str) -> napi::Result<Vec<u8>, ErrorCode> {
   fs::read(path)
     .await
-    .map_err(|e| napi::Error::from_reason(format!(
-      "Failed to read file at '{}': {}",
-      path, e
-    )))
+    .map_err(|e| ExtractError::read(format!("Failed to read file at '{}': {}", path, e)).into())
 }
 
 /// Offload PDF parsing to a blocking thread, with context
-async fn extract_text_from_bytes(path: &str, bytes: Vec<u8>) -> napi::Result<String> {
+async fn extract_text_from_bytes(path: &str, bytes: Vec<u8>) -> napi::Result<String, ErrorCode> {
   task::spawn_blocking(move || pdf_extract::extract_text_from_mem(&bytes))
     .await
-    .map_err(|e| napi::Error::from_reason(format!(
-      "PDF extraction thread panicked for '{}': {}",
-      path, e
-    )))?
-    .map_err(|e| napi::Error::from_reason(format!(
-      "Failed to extract PDF from '{}': {}",
-      path, e
-    )))
+    .map_err(|e| ExtractError::internal(format!("PDF extraction thread panicked for '{}': {}", path, e)))?
+    .map_err(|e| ExtractError::from_parse_failure(format!("Failed to extract PDF from '{}': {}", path, e)))
+    .map_err(napi::Error::from)
+}
+
+/// Split already-cleaned text into character-sized chunks, shared by every
+/// entry point that chunks by character count.
+fn build_chunks(
+  cleaned: &str,
+  chunk_size: u32,
+  chunk_overlap: u32,
+) -> napi::Result<Vec<TextChunk>, ErrorCode> {
+  let config = ChunkConfig::new(chunk_size as usize)
+    .with_overlap(chunk_overlap as usize)
+    .map_err(|e| ExtractError::parse(format!(
+      "Invalid chunk config (chunk_size={}, overlap={}): {}",
+      chunk_size, chunk_overlap, e
+    )))?;
+
+  let splitter = TextSplitter::new(config);
+  Ok(
+    splitter
+      .chunks(cleaned)
+      .into_iter()
+      .enumerate()
+      .map(|(i, text)| TextChunk {
+        id: i as u32,
+        text: text.to_string(),
+      })
+      .collect(),
+  )
 }
 
 /// Clean text by removing numeric-only lines
@@ -96,35 +269,10 @@ fn clean_text(text: &str) -> String {
     .join("\n")
 }
 
-/// Parse pages by detecting numeric page numbers
-fn split_text_into_pages(text: &str) -> Vec<Page> {
-  let mut pages = Vec::new();
-  let mut current_page = 0;
-  let mut buffer = String::new();
-
-  for (_i, line) in text.lines().enumerate() {
-    let trimmed = line.trim();
-
-    if let Ok(parsed_page) = trimmed.parse::<u32>() {
-      if current_page > 0 {
-        pages.push(Page {
-          page: current_page,
-          text: buffer.trim().to_string(),
-        });
-        buffer.clear();
-      }
-      current_page = parsed_page;
-    } else {
-      buffer.push_str(line);
-    }
-  }
-
-  if current_page > 0 && !buffer.trim().is_empty() {
-    pages.push(Page {
-      page: current_page,
-      text: buffer.trim().to_string(),
-    });
-  }
-
-  pages
+/// Re-decode likely Latin-1/Windows-1252 mojibake, apply Unicode NFC
+/// normalization, and strip leftover control characters, so corpora
+/// extracted from differently-encoded PDFs come back consistently as
+/// searchable UTF-8.
+fn clean_text_normalized(text: &str) -> String {
+  encoding::normalize_text(text)
 }
@@ -0,0 +1,69 @@
+use pdfium_render::prelude::{PdfRenderConfig, Pdfium};
+
+/// Tuning knobs for the OCR fallback path.
+#[napi(object)]
+pub struct OcrOptions {
+  /// If the cleaned text layer averages fewer characters per page than this,
+  /// the whole document is treated as image-only and every page is
+  /// re-extracted via OCR, replacing the text layer.
+  pub min_chars_per_page: u32,
+  /// Render resolution (DPI-equivalent pixel width) used when rasterizing a
+  /// page for Tesseract. Higher values improve accuracy at the cost of speed.
+  pub render_width: u32,
+}
+
+impl Default for OcrOptions {
+  fn default() -> Self {
+    OcrOptions {
+      min_chars_per_page: 32,
+      render_width: 1600,
+    }
+  }
+}
+
+/// Decide whether the text layer is too sparse to trust, and if so, recover
+/// each page's text by rasterizing it and running it through Tesseract.
+pub fn recover_with_ocr(
+  bytes: &[u8],
+  cleaned_text: &str,
+  options: &OcrOptions,
+) -> Result<String, String> {
+  // Load with pdfium up front, even for the cheap "is this sparse?" check,
+  // instead of counting pages with the stricter lopdf-based page extractor:
+  // the documents this feature targets (scanned/image-only PDFs, quirky
+  // slide exports) are exactly the ones most likely to trip a second,
+  // different parser, and we don't want that to hard-fail the OCR path the
+  // primary `pdf_extract` parse already got past.
+  let pdfium = Pdfium::default();
+  let document = pdfium
+    .load_pdf_from_byte_slice(bytes, None)
+    .map_err(|e| format!("Failed to load PDF for OCR rasterization: {}", e))?;
+
+  let page_count = document.pages().len().max(1);
+  let avg_chars_per_page = cleaned_text.chars().count() / page_count as usize;
+
+  if avg_chars_per_page as u32 >= options.min_chars_per_page {
+    return Ok(cleaned_text.to_string());
+  }
+
+  let render_config = PdfRenderConfig::new().set_target_width(options.render_width as i32);
+
+  let mut ocr_text = String::new();
+  for page in document.pages().iter() {
+    let bitmap = page
+      .render_with_config(&render_config)
+      .map_err(|e| format!("Failed to rasterize page for OCR: {}", e))?;
+
+    let page_text = ocr_image(&bitmap.as_image())?;
+    ocr_text.push_str(page_text.trim());
+    ocr_text.push('\n');
+  }
+
+  Ok(ocr_text.trim().to_string())
+}
+
+/// Run Tesseract OCR over a single rasterized page image.
+fn ocr_image(image: &image::DynamicImage) -> Result<String, String> {
+  rusty_tesseract::image_to_string(image, &rusty_tesseract::Args::default())
+    .map_err(|e| format!("Tesseract OCR failed: {}", e))
+}
@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+use std::panic::{self, AssertUnwindSafe};
+
+use pdf_extract::{output_doc, Document, MediaBox, OutputDev, OutputError, Transform};
+
+use crate::error::ExtractError;
+use crate::Page;
+
+/// Collects text per real PDF page by walking the document's own page tree,
+/// instead of guessing boundaries from numeric lines in the flattened text.
+struct PageTextOutput {
+  pages: Vec<Page>,
+  current_page: Option<u32>,
+  buffer: String,
+}
+
+impl PageTextOutput {
+  fn new() -> Self {
+    PageTextOutput {
+      pages: Vec::new(),
+      current_page: None,
+      buffer: String::new(),
+    }
+  }
+
+  fn flush_page(&mut self) {
+    if let Some(page) = self.current_page.take() {
+      self.pages.push(Page {
+        page,
+        text: self.buffer.trim().to_string(),
+      });
+      self.buffer.clear();
+    }
+  }
+}
+
+impl OutputDev for PageTextOutput {
+  fn begin_page(
+    &mut self,
+    page_num: u32,
+    _media_box: &MediaBox,
+    _art_box: Option<(f64, f64, f64, f64)>,
+  ) -> Result<(), OutputError> {
+    self.flush_page();
+    self.current_page = Some(page_num);
+    Ok(())
+  }
+
+  fn end_page(&mut self) -> Result<(), OutputError> {
+    Ok(())
+  }
+
+  fn output_character(
+    &mut self,
+    _trm: &Transform,
+    _x: f64,
+    _y: f64,
+    _spacing: f64,
+    _font_size: f64,
+    text: &str,
+  ) -> Result<(), OutputError> {
+    self.buffer.push_str(text);
+    Ok(())
+  }
+
+  fn begin_word(&mut self) -> Result<(), OutputError> {
+    Ok(())
+  }
+
+  fn end_word(&mut self) -> Result<(), OutputError> {
+    self.buffer.push(' ');
+    Ok(())
+  }
+
+  fn end_line(&mut self) -> Result<(), OutputError> {
+    self.buffer.push('\n');
+    Ok(())
+  }
+}
+
+// `Document` is `pdf_extract`'s own re-export of its bundled `lopdf`, not a
+// separately declared `lopdf` dependency -- `output_doc` requires the exact
+// same `lopdf::Document` type it was compiled against, and a second direct
+// `lopdf` dependency could resolve to a different semver and fail to unify.
+
+/// Extract one `Page` per real page in the document's page tree, in document order.
+pub fn extract_pages_from_bytes(bytes: &[u8]) -> Result<Vec<Page>, ExtractError> {
+  let doc = Document::load_mem(bytes)
+    .map_err(|e| ExtractError::from_parse_failure(format!("Failed to load PDF: {}", e)))?;
+
+  let mut output = PageTextOutput::new();
+  output_doc(&doc, &mut output)
+    .map_err(|e: OutputError| ExtractError::from_parse_failure(format!("Failed to walk page tree: {}", e)))?;
+  output.flush_page();
+
+  Ok(output.pages)
+}
+
+/// Like `extract_pages_from_bytes`, but tolerates a corrupt object aborting
+/// the document walk partway through, reporting unrecovered pages in `failed_pages`.
+pub fn extract_pages_best_effort(bytes: &[u8]) -> Result<(Vec<Page>, Vec<u32>), ExtractError> {
+  let doc = Document::load_mem(bytes)
+    .map_err(|e| ExtractError::from_parse_failure(format!("Failed to load PDF: {}", e)))?;
+
+  let mut output = PageTextOutput::new();
+
+  // `get_pages()` and `output_doc()` both walk the same malformed page tree
+  // this function exists to tolerate, so each runs inside its own
+  // catch_unwind: a panic mid-walk still leaves the expected page list.
+  let expected_pages = panic::catch_unwind(AssertUnwindSafe(|| {
+    doc.get_pages().into_keys().collect::<Vec<u32>>()
+  }));
+  let walk = panic::catch_unwind(AssertUnwindSafe(|| output_doc(&doc, &mut output)));
+
+  if let Ok(Ok(())) = walk {
+    output.flush_page();
+  }
+
+  let expected_pages = match expected_pages {
+    Ok(expected_pages) => expected_pages,
+    // `get_pages()` itself panicked before producing a page list, so the
+    // only pages we can account for are whatever made it into `output`.
+    Err(_) => output.pages.iter().map(|p| p.page).collect(),
+  };
+
+  let failed_pages = pages_not_in(expected_pages, &output.pages);
+
+  Ok((output.pages, failed_pages))
+}
+
+/// The expected page numbers that have no corresponding entry in `pages`.
+fn pages_not_in(expected_pages: Vec<u32>, pages: &[Page]) -> Vec<u32> {
+  let succeeded: HashSet<u32> = pages.iter().map(|p| p.page).collect();
+  expected_pages
+    .into_iter()
+    .filter(|page| !succeeded.contains(page))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn page(n: u32) -> Page {
+    Page {
+      page: n,
+      text: String::new(),
+    }
+  }
+
+  #[test]
+  fn pages_not_in_reports_missing_pages_only() {
+    let pages = vec![page(0), page(2)];
+    assert_eq!(pages_not_in(vec![0, 1, 2, 3], &pages), vec![1, 3]);
+  }
+
+  #[test]
+  fn pages_not_in_is_empty_when_everything_succeeded() {
+    let pages = vec![page(0), page(1)];
+    assert_eq!(pages_not_in(vec![0, 1], &pages), Vec::<u32>::new());
+  }
+}
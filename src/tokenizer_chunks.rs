@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use text_splitter::{ChunkConfig, TextSplitter};
+use tokenizers::Tokenizer;
+
+use crate::TextChunk;
+
+/// Tokenizers loaded by name, so batch-processing a corpus with the same
+/// `tokenizer_name` doesn't re-deserialize (and potentially re-download from
+/// the HuggingFace Hub) the same tokenizer on every document.
+static TOKENIZER_CACHE: OnceLock<Mutex<HashMap<String, Arc<Tokenizer>>>> = OnceLock::new();
+
+fn load_tokenizer(tokenizer_name: &str) -> Result<Arc<Tokenizer>, String> {
+  let cache = TOKENIZER_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+  let mut cache = cache.lock().unwrap();
+
+  if let Some(tokenizer) = cache.get(tokenizer_name) {
+    return Ok(tokenizer.clone());
+  }
+
+  let tokenizer = Tokenizer::from_pretrained(tokenizer_name, None)
+    .map_err(|e| format!("Failed to load tokenizer '{}': {}", tokenizer_name, e))?;
+  let tokenizer = Arc::new(tokenizer);
+  cache.insert(tokenizer_name.to_string(), tokenizer.clone());
+  Ok(tokenizer)
+}
+
+/// Split `text` into chunks sized by token count (via a HuggingFace
+/// tokenizer) rather than raw character count, so chunks slot directly into
+/// an LLM context window without post-hoc truncation. Loading a tokenizer by
+/// name hits the HuggingFace Hub cache (or the network, on a cold cache);
+/// the loaded tokenizer is cached in-process and reused across calls.
+pub fn chunk_by_tokens(
+  text: &str,
+  max_tokens: usize,
+  overlap: usize,
+  tokenizer_name: &str,
+) -> Result<Vec<TextChunk>, String> {
+  let tokenizer = load_tokenizer(tokenizer_name)?;
+
+  let config = ChunkConfig::new(max_tokens)
+    .with_sizer(tokenizer.as_ref())
+    .with_overlap(overlap)
+    .map_err(|e| format!(
+      "Invalid chunk config (max_tokens={}, overlap={}): {}",
+      max_tokens, overlap, e
+    ))?;
+
+  let splitter = TextSplitter::new(config);
+  Ok(
+    splitter
+      .chunks(text)
+      .into_iter()
+      .enumerate()
+      .map(|(i, text)| TextChunk {
+        id: i as u32,
+        text: text.to_string(),
+      })
+      .collect(),
+  )
+}